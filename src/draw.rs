@@ -0,0 +1,114 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::piece::piece::{Color, PieceType};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Hashes the repetition-relevant parts of a position: piece placement,
+/// side to move, castling rights and the en passant target square. Two
+/// positions with the same hash are the same position for threefold
+/// repetition purposes, matching FEN's own repetition semantics.
+pub(crate) fn position_key(board: &Board) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for y in 1..=8 {
+        for x in 1..=8 {
+            match board.get(Coordinates::new(x, y)) {
+                Some(piece) => {
+                    piece_type_code(piece.piece_type).hash(&mut hasher);
+                    color_code(piece.color).hash(&mut hasher);
+                }
+                None => 0u8.hash(&mut hasher),
+            }
+        }
+    }
+    color_code(board.color_to_move).hash(&mut hasher);
+    board.castling_rights.hash(&mut hasher);
+    board.en_passant.map(|sq| (sq.x, sq.y)).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn piece_type_code(piece_type: PieceType) -> u8 {
+    match piece_type {
+        PieceType::Pawn => 1,
+        PieceType::Knight => 2,
+        PieceType::Bishop => 3,
+        PieceType::Rook => 4,
+        PieceType::Queen => 5,
+        PieceType::King => 6,
+    }
+}
+
+fn color_code(color: Color) -> u8 {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// True when neither side has enough material to deliver checkmate: king
+/// vs king, king vs king+minor, or king+bishop vs king+bishop with both
+/// bishops on the same color of square.
+pub(crate) fn is_insufficient_material(board: &Board) -> bool {
+    let mut white_minors: Vec<(PieceType, Coordinates)> = Vec::new();
+    let mut black_minors: Vec<(PieceType, Coordinates)> = Vec::new();
+
+    for y in 1..=8 {
+        for x in 1..=8 {
+            let square = Coordinates::new(x, y);
+            let Some(piece) = board.get(square) else {
+                continue;
+            };
+            match piece.piece_type {
+                PieceType::King => {}
+                PieceType::Knight | PieceType::Bishop => {
+                    let minors = match piece.color {
+                        Color::White => &mut white_minors,
+                        Color::Black => &mut black_minors,
+                    };
+                    minors.push((piece.piece_type, square));
+                }
+                // Any pawn, rook or queen on the board is always sufficient.
+                _ => return false,
+            }
+        }
+    }
+
+    match (white_minors.len(), black_minors.len()) {
+        (0, 0) => true,
+        (1, 0) | (0, 1) => true,
+        (1, 1) => {
+            let (white_type, white_square) = white_minors[0];
+            let (black_type, black_square) = black_minors[0];
+            white_type == PieceType::Bishop
+                && black_type == PieceType::Bishop
+                && square_color(white_square) == square_color(black_square)
+        }
+        _ => false,
+    }
+}
+
+/// Light/dark color of a square, used to compare same-colored bishops.
+fn square_color(square: Coordinates) -> bool {
+    (square.x + square.y) % 2 == 0
+}
+
+/// Returns a human-readable draw reason if the game is drawn, given the
+/// current position's halfmove clock and a history of position hashes
+/// (including the current position).
+pub(crate) fn draw_reason(board: &Board, position_history: &[u64]) -> Option<String> {
+    if board.halfmove_clock >= 100 {
+        return Some("fifty-move rule".to_string());
+    }
+
+    if is_insufficient_material(board) {
+        return Some("insufficient material".to_string());
+    }
+
+    let current = position_history.last()?;
+    let repetitions = position_history.iter().filter(|key| *key == current).count();
+    if repetitions >= 3 {
+        return Some("threefold repetition".to_string());
+    }
+
+    None
+}
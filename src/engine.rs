@@ -0,0 +1,160 @@
+use crate::undo;
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::piece::piece::PieceType;
+
+/// Score magnitude used for a won position; actual mate scores are offset
+/// by the remaining search depth so that faster mates sort higher.
+const MATE_SCORE: i32 = 1_000_000;
+
+/// Sentinel bound for alpha-beta search. Must stay safely negatable
+/// (unlike `i32::MIN`, whose negation overflows `i32::MAX`).
+const INF: i32 = i32::MAX;
+
+fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => 1,
+        PieceType::Knight | PieceType::Bishop => 3,
+        PieceType::Rook => 5,
+        PieceType::Queen => 9,
+        PieceType::King => 0,
+    }
+}
+
+/// Material balance from the perspective of the side to move.
+fn evaluate(board: &Board) -> i32 {
+    let mut score = 0;
+    for y in 1..=8 {
+        for x in 1..=8 {
+            if let Some(piece) = board.get(Coordinates::new(x, y)) {
+                let value = piece_value(piece.piece_type);
+                if piece.color == board.color_to_move {
+                    score += value;
+                } else {
+                    score -= value;
+                }
+            }
+        }
+    }
+    score
+}
+
+/// Negamax search with alpha-beta pruning. Returns a score from the
+/// perspective of `board.color_to_move`. Recurses via make/unmake on a
+/// single board rather than cloning at every node.
+fn negamax(board: &mut Board, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+    let legal_moves = board.generate_legal_moves();
+
+    if legal_moves.is_empty() {
+        return if board.is_in_check() {
+            -MATE_SCORE - depth as i32
+        } else {
+            0
+        };
+    }
+
+    if depth == 0 {
+        return evaluate(board);
+    }
+
+    let mut best = -INF;
+    for (from, to) in legal_moves {
+        let record = match undo::apply_move_tracked(board, from, to) {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+        let score = -negamax(board, depth - 1, -beta, -alpha);
+        undo::unmake_move(board, record);
+
+        if score > best {
+            best = score;
+        }
+        if best > alpha {
+            alpha = best;
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+/// Finds the engine's preferred move for the current position by searching
+/// `depth` total plies with negamax/alpha-beta (the root move counts as the
+/// first ply). Returns `None` when there are no legal moves (checkmate or
+/// stalemate). `board` is restored to its original state before returning.
+pub fn find_best_move(board: &mut Board, depth: u32) -> Option<(Coordinates, Coordinates)> {
+    let legal_moves = board.generate_legal_moves();
+    if legal_moves.is_empty() {
+        return None;
+    }
+
+    let mut best_move = legal_moves[0];
+    let mut best_score = -INF;
+    let mut alpha = -INF;
+    let beta = INF;
+
+    for (from, to) in legal_moves {
+        let record = match undo::apply_move_tracked(board, from, to) {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+        let score = -negamax(board, depth.saturating_sub(1), -beta, -alpha);
+        undo::unmake_move(board, record);
+
+        if score > best_score {
+            best_score = score;
+            best_move = (from, to);
+        }
+        if best_score > alpha {
+            alpha = best_score;
+        }
+    }
+
+    Some(best_move)
+}
+
+/// Counts the leaf nodes reachable in exactly `depth` plies from `board`,
+/// via make/unmake rather than cloning. Useful for cross-checking move
+/// generation against known perft results.
+pub fn perft(board: &mut Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let legal_moves = board.generate_legal_moves();
+    if depth == 1 {
+        return legal_moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for (from, to) in legal_moves {
+        let record = match undo::apply_move_tracked(board, from, to) {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+        nodes += perft(board, depth - 1);
+        undo::unmake_move(board, record);
+    }
+    nodes
+}
+
+/// Runs `perft(depth - 1)` below each root move, returning the per-move
+/// subtree node counts. Useful for finding which root move a perft
+/// discrepancy comes from.
+pub fn perft_divide(board: &mut Board, depth: u32) -> Vec<(Coordinates, Coordinates, u64)> {
+    let legal_moves = board.generate_legal_moves();
+    let mut divide = Vec::with_capacity(legal_moves.len());
+
+    for (from, to) in legal_moves {
+        let record = match undo::apply_move_tracked(board, from, to) {
+            Ok(record) => record,
+            Err(_) => continue,
+        };
+        let nodes = perft(board, depth.saturating_sub(1));
+        undo::unmake_move(board, record);
+        divide.push((from, to, nodes));
+    }
+
+    divide
+}
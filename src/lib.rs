@@ -3,6 +3,18 @@ use wasm_bindgen::prelude::*;
 // Re-export the WASM interface
 pub mod wasm;
 
+// Move search for the built-in AI opponent
+pub mod engine;
+
+// Make/unmake support shared by the search and the WASM surface
+mod undo;
+
+// Standard algebraic notation for recording and exporting games
+mod notation;
+
+// Draw detection: repetition, the fifty-move rule, and insufficient material
+mod draw;
+
 // Initialize panic hook for better error messages
 #[wasm_bindgen(start)]
 pub fn main() {
@@ -0,0 +1,111 @@
+use fenex::chess::board::board::Board;
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::piece::piece::{Color, PieceType};
+
+fn piece_letter(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Knight => 'N',
+        PieceType::Bishop => 'B',
+        PieceType::Rook => 'R',
+        PieceType::Queen => 'Q',
+        PieceType::King => 'K',
+        PieceType::Pawn => '\0',
+    }
+}
+
+fn promotion_rank(color: Color) -> i8 {
+    if color == Color::White {
+        8
+    } else {
+        1
+    }
+}
+
+/// Builds the standard algebraic notation token for a move, using `pre_move`
+/// (the board before the move, for piece identity and disambiguation) and
+/// `post_move` (the board after the move, for the `+`/`#` suffix).
+pub(crate) fn move_to_san(
+    pre_move: &Board,
+    post_move: &Board,
+    from: Coordinates,
+    to: Coordinates,
+    promotion: Option<PieceType>,
+) -> String {
+    let Some(moved_piece) = pre_move.get(from) else {
+        return String::new();
+    };
+
+    let mut san = if moved_piece.piece_type == PieceType::King && (from.x - to.x).abs() == 2 {
+        if to.x == 7 {
+            "O-O".to_string()
+        } else {
+            "O-O-O".to_string()
+        }
+    } else {
+        let is_en_passant = moved_piece.piece_type == PieceType::Pawn
+            && Some(to) == pre_move.en_passant
+            && pre_move.get(to).is_none()
+            && (from.x - to.x).abs() == 1;
+        let is_capture = pre_move.get(to).is_some() || is_en_passant;
+
+        let mut token = String::new();
+        if moved_piece.piece_type != PieceType::Pawn {
+            token.push(piece_letter(moved_piece.piece_type));
+
+            // Disambiguate against other legal moves by a same-type piece
+            // landing on the same square.
+            let legal_moves = pre_move.generate_legal_moves();
+            let others: Vec<Coordinates> = legal_moves
+                .iter()
+                .filter(|(other_from, other_to)| *other_to == to && *other_from != from)
+                .filter_map(|(other_from, _)| {
+                    pre_move
+                        .get(*other_from)
+                        .filter(|p| p.piece_type == moved_piece.piece_type)
+                        .map(|_| *other_from)
+                })
+                .collect();
+
+            if !others.is_empty() {
+                let same_file = others.iter().any(|c| c.x == from.x);
+                let same_rank = others.iter().any(|c| c.y == from.y);
+                if !same_file {
+                    token.push(from.to_file());
+                } else if !same_rank {
+                    token.push(from.to_rank());
+                } else {
+                    token.push(from.to_file());
+                    token.push(from.to_rank());
+                }
+            }
+        } else if is_capture {
+            token.push(from.to_file());
+        }
+
+        if is_capture {
+            token.push('x');
+        }
+        token.push(to.to_file());
+        token.push(to.to_rank());
+
+        if moved_piece.piece_type == PieceType::Pawn && to.y == promotion_rank(moved_piece.color) {
+            token.push('=');
+            token.push(match promotion {
+                Some(PieceType::Rook) => 'R',
+                Some(PieceType::Bishop) => 'B',
+                Some(PieceType::Knight) => 'N',
+                _ => 'Q',
+            });
+        }
+
+        token
+    };
+
+    if post_move.is_checkmate() {
+        san.push('#');
+    } else if post_move.is_in_check() {
+        san.push('+');
+    }
+
+    san
+}
@@ -0,0 +1,242 @@
+use fenex::chess::board::board::{Board, Piece};
+use fenex::chess::board::coordinates::Coordinates;
+use fenex::chess::piece::piece::{Color, PieceType};
+
+/// A reversible record of one move applied directly to a `Board` via
+/// `apply_move_tracked`. Feeding it back into `unmake_move` restores the
+/// board to exactly the state it was in before the move, without cloning.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Undo {
+    pub(crate) from: Coordinates,
+    pub(crate) to: Coordinates,
+    /// The piece type the move promoted to, if it was a promotion. Lets
+    /// `redo_last_move` replay the move with the originally chosen piece
+    /// instead of always defaulting to a queen.
+    pub(crate) promoted_to: Option<PieceType>,
+    moved_piece: Piece,
+    captured: Option<(Coordinates, Piece)>,
+    previous_castling_rights: [bool; 4],
+    previous_en_passant: Option<Coordinates>,
+    previous_halfmove_clock: u32,
+}
+
+fn promotion_rank(color: Color) -> i8 {
+    if color == Color::White {
+        8
+    } else {
+        1
+    }
+}
+
+/// Applies `from -> to` directly to `board`'s squares and bookkeeping
+/// fields, bypassing fenex's own `generate_legal_moves` re-check inside
+/// `apply_move`. Callers must have already validated the move against
+/// `board.generate_legal_moves()`. Always promotes to a queen, matching
+/// `Board::apply_move`'s default; use `apply_move_tracked_promotion` for a
+/// chosen promotion piece.
+pub(crate) fn apply_move_tracked(
+    board: &mut Board,
+    from: Coordinates,
+    to: Coordinates,
+) -> Result<Undo, &'static str> {
+    apply_move_tracked_promotion(board, from, to, PieceType::Queen)
+}
+
+/// Same as `apply_move_tracked`, but promotes a pawn reaching the back rank
+/// to `promotion_piece` instead of always to a queen. Non-promotion moves
+/// ignore `promotion_piece`. This is the tracked counterpart to
+/// `Board::promote_to_*`, so under-promotions also get a valid `Undo`.
+pub(crate) fn apply_move_tracked_promotion(
+    board: &mut Board,
+    from: Coordinates,
+    to: Coordinates,
+    promotion_piece: PieceType,
+) -> Result<Undo, &'static str> {
+    let moved_piece = board.get(from).ok_or("No piece at source square")?;
+
+    let previous_castling_rights = board.castling_rights;
+    let previous_en_passant = board.en_passant;
+    let previous_halfmove_clock = board.halfmove_clock;
+
+    let is_en_passant = moved_piece.piece_type == PieceType::Pawn
+        && Some(to) == previous_en_passant
+        && board.get(to).is_none()
+        && (from.x - to.x).abs() == 1
+        && (from.y - to.y).abs() == 1;
+
+    let captured = if is_en_passant {
+        let captured_y = if moved_piece.color == Color::White {
+            to.y - 1
+        } else {
+            to.y + 1
+        };
+        let captured_square = Coordinates::new(to.x, captured_y);
+        let captured_piece = board.get(captured_square);
+        board.set(captured_square, None);
+        captured_piece.map(|p| (captured_square, p))
+    } else {
+        board.get(to).map(|p| (to, p))
+    };
+
+    // Move the rook along with the king on castling.
+    let is_castle = moved_piece.piece_type == PieceType::King && (from.x - to.x).abs() == 2;
+    if is_castle {
+        let rank = from.y;
+        if to.x == 7 {
+            board.set(Coordinates::new(8, rank), None);
+            board.set(
+                Coordinates::new(6, rank),
+                Some(Piece {
+                    piece_type: PieceType::Rook,
+                    color: moved_piece.color,
+                    has_moved: true,
+                }),
+            );
+        } else if to.x == 3 {
+            board.set(Coordinates::new(1, rank), None);
+            board.set(
+                Coordinates::new(4, rank),
+                Some(Piece {
+                    piece_type: PieceType::Rook,
+                    color: moved_piece.color,
+                    has_moved: true,
+                }),
+            );
+        }
+    }
+
+    board.set(from, None);
+
+    let was_promotion =
+        moved_piece.piece_type == PieceType::Pawn && to.y == promotion_rank(moved_piece.color);
+    if was_promotion {
+        board.set(
+            to,
+            Some(Piece {
+                piece_type: promotion_piece,
+                color: moved_piece.color,
+                has_moved: true,
+            }),
+        );
+    } else {
+        board.set(
+            to,
+            Some(Piece {
+                has_moved: true,
+                ..moved_piece
+            }),
+        );
+    }
+
+    let mut castling_rights = previous_castling_rights;
+    match (moved_piece.piece_type, moved_piece.color) {
+        (PieceType::King, Color::White) => {
+            castling_rights[0] = false;
+            castling_rights[1] = false;
+        }
+        (PieceType::King, Color::Black) => {
+            castling_rights[2] = false;
+            castling_rights[3] = false;
+        }
+        (PieceType::Rook, Color::White) if from.x == 1 && from.y == 1 => {
+            castling_rights[1] = false
+        }
+        (PieceType::Rook, Color::White) if from.x == 8 && from.y == 1 => {
+            castling_rights[0] = false
+        }
+        (PieceType::Rook, Color::Black) if from.x == 1 && from.y == 8 => {
+            castling_rights[3] = false
+        }
+        (PieceType::Rook, Color::Black) if from.x == 8 && from.y == 8 => {
+            castling_rights[2] = false
+        }
+        _ => {}
+    }
+    // A captured rook on its home square also forfeits that side's rights.
+    if let Some((square, captured_piece)) = captured {
+        if captured_piece.piece_type == PieceType::Rook {
+            match (captured_piece.color, square.x, square.y) {
+                (Color::White, 1, 1) => castling_rights[1] = false,
+                (Color::White, 8, 1) => castling_rights[0] = false,
+                (Color::Black, 1, 8) => castling_rights[3] = false,
+                (Color::Black, 8, 8) => castling_rights[2] = false,
+                _ => {}
+            }
+        }
+    }
+    board.castling_rights = castling_rights;
+
+    board.en_passant = if moved_piece.piece_type == PieceType::Pawn && (from.y - to.y).abs() == 2 {
+        Some(Coordinates::new(from.x, (from.y + to.y) / 2))
+    } else {
+        None
+    };
+
+    // The clock resets on a pawn move or a capture, otherwise it ticks up.
+    board.halfmove_clock = if moved_piece.piece_type == PieceType::Pawn || captured.is_some() {
+        0
+    } else {
+        previous_halfmove_clock + 1
+    };
+
+    board.color_to_move = match board.color_to_move {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    };
+
+    Ok(Undo {
+        from,
+        to,
+        promoted_to: was_promotion.then_some(promotion_piece),
+        moved_piece,
+        captured,
+        previous_castling_rights,
+        previous_en_passant,
+        previous_halfmove_clock,
+    })
+}
+
+/// Restores `board` to the state it was in before the move recorded by
+/// `undo` was applied.
+pub(crate) fn unmake_move(board: &mut Board, undo: Undo) {
+    board.color_to_move = match board.color_to_move {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    };
+
+    board.set(undo.to, None);
+    board.set(undo.from, Some(undo.moved_piece));
+
+    if let Some((square, piece)) = undo.captured {
+        board.set(square, Some(piece));
+    }
+
+    if undo.moved_piece.piece_type == PieceType::King && (undo.from.x - undo.to.x).abs() == 2 {
+        let rank = undo.from.y;
+        if undo.to.x == 7 {
+            board.set(Coordinates::new(6, rank), None);
+            board.set(
+                Coordinates::new(8, rank),
+                Some(Piece {
+                    piece_type: PieceType::Rook,
+                    color: undo.moved_piece.color,
+                    has_moved: false,
+                }),
+            );
+        } else if undo.to.x == 3 {
+            board.set(Coordinates::new(4, rank), None);
+            board.set(
+                Coordinates::new(1, rank),
+                Some(Piece {
+                    piece_type: PieceType::Rook,
+                    color: undo.moved_piece.color,
+                    has_moved: false,
+                }),
+            );
+        }
+    }
+
+    board.castling_rights = undo.previous_castling_rights;
+    board.en_passant = undo.previous_en_passant;
+    board.halfmove_clock = undo.previous_halfmove_clock;
+}
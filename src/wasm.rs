@@ -1,6 +1,10 @@
+use crate::draw;
+use crate::engine;
+use crate::notation;
+use crate::undo::{self, Undo};
 use fenex::chess::board::board::Board;
 use fenex::chess::board::coordinates::Coordinates;
-use fenex::chess::piece::piece::Color;
+use fenex::chess::piece::piece::{Color, PieceType};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -9,12 +13,55 @@ use wasm_bindgen::prelude::*;
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
+
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn now_ms() -> f64;
 }
 
 macro_rules! console_log {
     ($($t:tt)*) => (unsafe { log(&format_args!($($t)*).to_string()) })
 }
 
+/// Parses a UCI long-algebraic move string such as `e2e4` or `e7e8q` into
+/// from/to squares and an optional promotion piece.
+fn parse_uci_move(uci: &str) -> Option<(Coordinates, Coordinates, Option<PieceType>)> {
+    if uci.len() != 4 && uci.len() != 5 {
+        return None;
+    }
+    let from = Coordinates::from_notation_string(&uci[0..2]).ok()?;
+    let to = Coordinates::from_notation_string(&uci[2..4]).ok()?;
+    let promotion = match uci.as_bytes().get(4) {
+        None => None,
+        Some(b'q') | Some(b'Q') => Some(PieceType::Queen),
+        Some(b'r') | Some(b'R') => Some(PieceType::Rook),
+        Some(b'b') | Some(b'B') => Some(PieceType::Bishop),
+        Some(b'n') | Some(b'N') => Some(PieceType::Knight),
+        Some(_) => return None,
+    };
+    Some((from, to, promotion))
+}
+
+/// Formats a move as a UCI long-algebraic string, e.g. `e2e4` or `e7e8q`.
+fn move_to_uci(from: Coordinates, to: Coordinates, promotion: Option<PieceType>) -> String {
+    let mut uci = format!(
+        "{}{}{}{}",
+        from.to_file(),
+        from.to_rank(),
+        to.to_file(),
+        to.to_rank()
+    );
+    if let Some(piece) = promotion {
+        uci.push(match piece {
+            PieceType::Queen => 'q',
+            PieceType::Rook => 'r',
+            PieceType::Bishop => 'b',
+            PieceType::Knight => 'n',
+            _ => 'q',
+        });
+    }
+    uci
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Move {
     pub from_row: usize,
@@ -31,11 +78,20 @@ pub struct GameState {
     pub is_checkmate: bool,
     pub is_stalemate: bool,
     pub fen: String,
+    pub is_draw: bool,
+    pub draw_reason: Option<String>,
+    pub halfmove_clock: u32,
 }
 
 #[wasm_bindgen]
 pub struct ChessGame {
     board: Board,
+    history: Vec<Undo>,
+    redo_stack: Vec<Undo>,
+    last_move: Option<(Coordinates, Coordinates, Option<PieceType>)>,
+    san_history: Vec<String>,
+    redo_san_stack: Vec<String>,
+    position_history: Vec<u64>,
 }
 
 #[wasm_bindgen]
@@ -45,14 +101,34 @@ impl ChessGame {
         console_log!("Creating new chess game");
         let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
             .unwrap_or_else(|_| Board::new());
-        ChessGame { board }
+        let position_history = vec![draw::position_key(&board)];
+        ChessGame {
+            board,
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+            last_move: None,
+            san_history: Vec::new(),
+            redo_san_stack: Vec::new(),
+            position_history,
+        }
     }
 
     #[wasm_bindgen]
     pub fn from_fen(fen: &str) -> Result<ChessGame, JsValue> {
         console_log!("Loading from FEN: {}", fen);
         match Board::from_fen(fen) {
-            Ok(board) => Ok(ChessGame { board }),
+            Ok(board) => {
+                let position_history = vec![draw::position_key(&board)];
+                Ok(ChessGame {
+                    board,
+                    history: Vec::new(),
+                    redo_stack: Vec::new(),
+                    last_move: None,
+                    san_history: Vec::new(),
+                    redo_san_stack: Vec::new(),
+                    position_history,
+                })
+            }
             Err(e) => Err(JsValue::from_str(&format!("Invalid FEN: {:?}", e))),
         }
     }
@@ -86,6 +162,8 @@ impl ChessGame {
         let in_check = self.board.is_in_check();
         let is_checkmate = self.board.is_checkmate();
         let is_stalemate = self.board.is_stalemate();
+        let draw_reason = draw::draw_reason(&self.board, &self.position_history);
+        let is_draw = draw_reason.is_some();
 
         let state = GameState {
             board: board_state,
@@ -94,6 +172,9 @@ impl ChessGame {
             is_checkmate,
             is_stalemate,
             fen: self.board.to_fen(),
+            is_draw,
+            draw_reason,
+            halfmove_clock: self.board.halfmove_clock,
         };
 
         serde_wasm_bindgen::to_value(&state).unwrap()
@@ -194,9 +275,22 @@ impl ChessGame {
             console_log!("Moving piece: {:?} {:?}", piece.color, piece.piece_type);
         }
 
-        // Apply the move
-        match self.board.apply_move(from_coords, to_coords) {
-            Ok(_) => {
+        // Apply the move via the tracked make/unmake path so it can be undone
+        let pre_move_board = self.board.clone();
+        match undo::apply_move_tracked(&mut self.board, from_coords, to_coords) {
+            Ok(record) => {
+                self.history.push(record);
+                self.redo_stack.clear();
+                self.last_move = Some((from_coords, to_coords, record.promoted_to));
+                self.san_history.push(notation::move_to_san(
+                    &pre_move_board,
+                    &self.board,
+                    from_coords,
+                    to_coords,
+                    None,
+                ));
+                self.redo_san_stack.clear();
+                self.position_history.push(draw::position_key(&self.board));
                 console_log!("Move successful!");
 
                 // Check if the move resulted in check
@@ -253,7 +347,7 @@ impl ChessGame {
 
             // Check if it's a pawn moving to the promotion rank
             let is_promotion = match piece.piece_type {
-                fenex::chess::piece::piece::PieceType::Pawn => match piece.color {
+                PieceType::Pawn => match piece.color {
                     Color::White => to_coords.y == 8,
                     Color::Black => to_coords.y == 1,
                 },
@@ -291,39 +385,45 @@ impl ChessGame {
         }
         
         console_log!("Using fenex 0.1.10 specific promotion methods");
-        
-        // Use fenex 0.1.10 specific promotion methods
-        let result = match promotion_piece.to_lowercase().as_str() {
-            "queen" => {
-                console_log!("Promoting to Queen");
-                self.board.promote_to_queen(from_coords, to_coords)
-            },
-            "rook" => {
-                console_log!("Promoting to Rook");
-                self.board.promote_to_rook(from_coords, to_coords)
-            },
-            "bishop" => {
-                console_log!("Promoting to Bishop");
-                self.board.promote_to_bishop(from_coords, to_coords)
-            },
-            "knight" => {
-                console_log!("Promoting to Knight");
-                self.board.promote_to_knight(from_coords, to_coords)
-            },
+
+        let pre_move_board = self.board.clone();
+
+        let promotion_type = match promotion_piece.to_lowercase().as_str() {
+            "queen" => PieceType::Queen,
+            "rook" => PieceType::Rook,
+            "bishop" => PieceType::Bishop,
+            "knight" => PieceType::Knight,
             _ => {
                 console_log!("Unknown piece type, defaulting to Queen");
-                self.board.promote_to_queen(from_coords, to_coords)
+                PieceType::Queen
             }
         };
-        
-        match result {
-            Ok(_) => {
+
+        match undo::apply_move_tracked_promotion(
+            &mut self.board,
+            from_coords,
+            to_coords,
+            promotion_type,
+        ) {
+            Ok(record) => {
                 console_log!("Promotion move successful! (using fenex 0.1.10 specific methods)");
-                
+                self.history.push(record);
+                self.redo_stack.clear();
+
                 // Log what piece is actually at the destination after the move
                 if let Some(piece) = self.board.get(to_coords) {
                     console_log!("Promoted piece is: {:?} {:?}", piece.color, piece.piece_type);
                     console_log!("SUCCESS: Promoted to {:?}!", piece.piece_type);
+                    self.last_move = Some((from_coords, to_coords, Some(piece.piece_type)));
+                    self.san_history.push(notation::move_to_san(
+                        &pre_move_board,
+                        &self.board,
+                        from_coords,
+                        to_coords,
+                        Some(piece.piece_type),
+                    ));
+                    self.redo_san_stack.clear();
+                    self.position_history.push(draw::position_key(&self.board));
                 } else {
                     console_log!("WARNING: No piece found at promotion square after move");
                 }
@@ -361,7 +461,7 @@ impl ChessGame {
         // Check if there's a pawn at the source position
         if let Some(piece) = self.board.get(from_coords) {
             match piece.piece_type {
-                fenex::chess::piece::piece::PieceType::Pawn => {
+                PieceType::Pawn => {
                     // Check if moving to promotion rank
                     match piece.color {
                         Color::White => to_coords.y == 8,
@@ -380,6 +480,74 @@ impl ChessGame {
         console_log!("Resetting board");
         self.board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")
             .unwrap_or_else(|_| Board::new());
+        self.history.clear();
+        self.redo_stack.clear();
+        self.last_move = None;
+        self.san_history.clear();
+        self.redo_san_stack.clear();
+        self.position_history = vec![draw::position_key(&self.board)];
+    }
+
+    /// Reverts the last move made with `make_move`/`make_promotion_move`/
+    /// `make_uci_move`, restoring the board without cloning it. Returns
+    /// `false` if there is no move to undo.
+    #[wasm_bindgen]
+    pub fn undo_last_move(&mut self) -> bool {
+        match self.history.pop() {
+            Some(record) => {
+                console_log!("Undoing last move");
+                undo::unmake_move(&mut self.board, record);
+                self.redo_stack.push(record);
+                self.last_move = self.history.last().map(|r| (r.from, r.to, r.promoted_to));
+                if let Some(san) = self.san_history.pop() {
+                    self.redo_san_stack.push(san);
+                }
+                self.position_history.pop();
+                true
+            }
+            None => {
+                console_log!("Nothing to undo");
+                false
+            }
+        }
+    }
+
+    /// Re-applies the most recently undone move. Returns `false` if there is
+    /// no move to redo.
+    #[wasm_bindgen]
+    pub fn redo_last_move(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(record) => {
+                console_log!("Redoing last undone move");
+                let promotion = record.promoted_to.unwrap_or(PieceType::Queen);
+                match undo::apply_move_tracked_promotion(
+                    &mut self.board,
+                    record.from,
+                    record.to,
+                    promotion,
+                ) {
+                    Ok(new_record) => {
+                        self.history.push(new_record);
+                        self.last_move =
+                            Some((new_record.from, new_record.to, new_record.promoted_to));
+                        if let Some(san) = self.redo_san_stack.pop() {
+                            self.san_history.push(san);
+                        }
+                        self.position_history.push(draw::position_key(&self.board));
+                        true
+                    }
+                    Err(e) => {
+                        console_log!("Redo failed: {:?}", e);
+                        self.redo_stack.push(record);
+                        false
+                    }
+                }
+            }
+            None => {
+                console_log!("Nothing to redo");
+                false
+            }
+        }
     }
 
     #[wasm_bindgen]
@@ -388,7 +556,7 @@ impl ChessGame {
         console_log!("Testing FEN: {}", fen);
 
         // Load the position
-        let board = match Board::from_fen(fen) {
+        let mut board = match Board::from_fen(fen) {
             Ok(b) => b,
             Err(e) => {
                 console_log!("Invalid FEN: {:?}", e);
@@ -406,10 +574,9 @@ impl ChessGame {
         let mut check_giving_moves = Vec::new();
         let mut debug_info = Vec::new();
 
-        // Test each move to see if it gives check
+        // Test each move to see if it gives check, applying and unmaking on
+        // a single board instead of cloning for every candidate move
         for (from, to) in &legal_moves {
-            let mut test_board = board.clone();
-
             // Get piece info
             let piece_info = if let Some(piece) = board.get(*from) {
                 format!("{:?} {:?}", piece.color, piece.piece_type)
@@ -418,10 +585,10 @@ impl ChessGame {
             };
 
             // Apply the move
-            match test_board.apply_move(*from, *to) {
-                Ok(_) => {
+            match undo::apply_move_tracked(&mut board, *from, *to) {
+                Ok(record) => {
                     // Check if opponent is now in check
-                    if test_board.is_in_check() {
+                    if board.is_in_check() {
                         let move_desc = format!(
                             "{} from ({},{}) to ({},{}) gives check!",
                             piece_info, from.x, from.y, to.x, to.y
@@ -430,6 +597,7 @@ impl ChessGame {
                         check_giving_moves.push(move_desc.clone());
                         debug_info.push(move_desc);
                     }
+                    undo::unmake_move(&mut board, record);
                 }
                 Err(e) => {
                     console_log!("Move failed: {:?}", e);
@@ -444,7 +612,7 @@ impl ChessGame {
     }
 
     #[wasm_bindgen]
-    pub fn test_specific_check_move(&self, from_x: i8, from_y: i8, to_x: i8, to_y: i8) -> JsValue {
+    pub fn test_specific_check_move(&mut self, from_x: i8, from_y: i8, to_x: i8, to_y: i8) -> JsValue {
         console_log!("=== TESTING SPECIFIC MOVE ===");
         console_log!("Move: ({},{}) to ({},{})", from_x, from_y, to_x, to_y);
 
@@ -469,13 +637,14 @@ impl ChessGame {
             return serde_wasm_bindgen::to_value(&"Move not legal").unwrap();
         }
 
-        // Test the move
-        let mut test_board = self.board.clone();
-        match test_board.apply_move(from_coords, to_coords) {
-            Ok(_) => {
-                let gives_check = test_board.is_in_check();
+        // Test the move on the real board, then immediately unmake it so the
+        // game state is unaffected by this probe
+        match undo::apply_move_tracked(&mut self.board, from_coords, to_coords) {
+            Ok(record) => {
+                let gives_check = self.board.is_in_check();
                 console_log!("Move successful, gives check: {}", gives_check);
-                console_log!("Result FEN: {}", test_board.to_fen());
+                console_log!("Result FEN: {}", self.board.to_fen());
+                undo::unmake_move(&mut self.board, record);
 
                 let result = format!("Move successful, gives check: {}", gives_check);
                 serde_wasm_bindgen::to_value(&result).unwrap()
@@ -539,4 +708,212 @@ impl ChessGame {
             }
         }
     }
+
+    /// Returns the engine's recommended move for the current position,
+    /// searching up to `depth` plies with negamax/alpha-beta. `time_budget_ms`,
+    /// when given, limits thinking time via iterative deepening: the engine
+    /// searches depth 1, 2, 3, ... and starts no further depth once the budget
+    /// is spent, returning the deepest completed result. Depth 1 always runs,
+    /// and a depth already under way is not interrupted, so the total time
+    /// can exceed the budget. Returns `null` if there is no legal move
+    /// (checkmate or stalemate).
+    #[wasm_bindgen]
+    pub fn get_best_move(&mut self, depth: u32, time_budget_ms: Option<u32>) -> JsValue {
+        console_log!("Searching for best move at depth {}", depth);
+
+        let deadline = time_budget_ms.map(|ms| now_ms() + ms as f64);
+        let max_depth = depth.max(1);
+        let mut best: Option<(Coordinates, Coordinates)> = None;
+
+        for d in 1..=max_depth {
+            if let Some(deadline) = deadline {
+                if d > 1 && now_ms() >= deadline {
+                    break;
+                }
+            }
+            match engine::find_best_move(&mut self.board, d) {
+                Some(mv) => best = Some(mv),
+                None => break,
+            }
+        }
+
+        match best {
+            Some((from, to)) => {
+                console_log!("Best move: ({},{}) -> ({},{})", from.x, from.y, to.x, to.y);
+                serde_wasm_bindgen::to_value(&Move {
+                    from_row: (8 - from.y) as usize,
+                    from_col: (from.x - 1) as usize,
+                    to_row: (8 - to.y) as usize,
+                    to_col: (to.x - 1) as usize,
+                })
+                .unwrap()
+            }
+            None => {
+                console_log!("No legal move available");
+                JsValue::NULL
+            }
+        }
+    }
+
+    /// Makes a move given as a UCI long-algebraic string (`e2e4`, `g1f3`,
+    /// `e7e8q`). The optional trailing letter selects the promotion piece.
+    #[wasm_bindgen]
+    pub fn make_uci_move(&mut self, uci: &str) -> bool {
+        console_log!("Attempting UCI move: {}", uci);
+
+        let (from, to, promotion) = match parse_uci_move(uci) {
+            Some(parsed) => parsed,
+            None => {
+                console_log!("Invalid UCI move string: {}", uci);
+                return false;
+            }
+        };
+
+        let legal_moves = self.board.generate_legal_moves();
+        if !legal_moves.contains(&(from, to)) {
+            console_log!("UCI move rejected: not in legal moves list");
+            return false;
+        }
+
+        if let Some(piece) = promotion {
+            let pre_move_board = self.board.clone();
+            return match undo::apply_move_tracked_promotion(&mut self.board, from, to, piece) {
+                Ok(record) => {
+                    self.history.push(record);
+                    self.redo_stack.clear();
+                    self.last_move = Some((from, to, record.promoted_to));
+                    self.san_history.push(notation::move_to_san(
+                        &pre_move_board,
+                        &self.board,
+                        from,
+                        to,
+                        Some(piece),
+                    ));
+                    self.redo_san_stack.clear();
+                    self.position_history.push(draw::position_key(&self.board));
+                    true
+                }
+                Err(e) => {
+                    console_log!("UCI promotion move failed: {:?}", e);
+                    false
+                }
+            };
+        }
+
+        let pre_move_board = self.board.clone();
+        match undo::apply_move_tracked(&mut self.board, from, to) {
+            Ok(record) => {
+                self.history.push(record);
+                self.redo_stack.clear();
+                self.last_move = Some((from, to, record.promoted_to));
+                self.san_history.push(notation::move_to_san(
+                    &pre_move_board,
+                    &self.board,
+                    from,
+                    to,
+                    None,
+                ));
+                self.redo_san_stack.clear();
+                self.position_history.push(draw::position_key(&self.board));
+                true
+            }
+            Err(e) => {
+                console_log!("UCI move failed: {:?}", e);
+                false
+            }
+        }
+    }
+
+    /// Returns the legal destination squares for `square` (e.g. `"e2"`) as
+    /// UCI long-algebraic strings.
+    #[wasm_bindgen]
+    pub fn get_valid_moves_uci(&self, square: &str) -> JsValue {
+        console_log!("Getting UCI valid moves for {}", square);
+
+        let from = match Coordinates::from_notation_string(square) {
+            Ok(coords) => coords,
+            Err(e) => {
+                console_log!("Invalid square: {:?}", e);
+                return serde_wasm_bindgen::to_value(&Vec::<String>::new()).unwrap();
+            }
+        };
+
+        let legal_moves = self.board.generate_legal_moves();
+        let uci_moves: Vec<String> = legal_moves
+            .iter()
+            .filter(|(mv_from, _)| *mv_from == from)
+            .map(|(_, to)| move_to_uci(from, *to, None))
+            .collect();
+
+        serde_wasm_bindgen::to_value(&uci_moves).unwrap()
+    }
+
+    /// Returns the most recently applied move as a UCI long-algebraic
+    /// string, or `undefined` if no move has been made yet.
+    #[wasm_bindgen]
+    pub fn last_move_uci(&self) -> Option<String> {
+        self.last_move
+            .map(|(from, to, promotion)| move_to_uci(from, to, promotion))
+    }
+
+    /// Returns the game's moves so far in standard algebraic notation, one
+    /// entry per ply.
+    #[wasm_bindgen]
+    pub fn get_move_history(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.san_history).unwrap()
+    }
+
+    /// Renders the game so far as PGN movetext, including the result tag.
+    #[wasm_bindgen]
+    pub fn get_pgn(&self) -> String {
+        let mut movetext = String::new();
+        for (i, san) in self.san_history.iter().enumerate() {
+            if i % 2 == 0 {
+                if i > 0 {
+                    movetext.push(' ');
+                }
+                movetext.push_str(&format!("{}. ", i / 2 + 1));
+            } else {
+                movetext.push(' ');
+            }
+            movetext.push_str(san);
+        }
+
+        let result = if self.board.is_checkmate() {
+            match self.board.color_to_move {
+                Color::White => "0-1",
+                Color::Black => "1-0",
+            }
+        } else if self.board.is_stalemate() {
+            "1/2-1/2"
+        } else {
+            "*"
+        };
+
+        if movetext.is_empty() {
+            result.to_string()
+        } else {
+            format!("{} {}", movetext, result)
+        }
+    }
+
+    /// Counts the leaf positions reachable in exactly `depth` plies from the
+    /// current position. Used to cross-check move generation against known
+    /// perft results.
+    #[wasm_bindgen]
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        engine::perft(&mut self.board, depth)
+    }
+
+    /// Runs `perft(depth - 1)` below each legal root move, returning a map
+    /// of root move (UCI long-algebraic) to subtree node count.
+    #[wasm_bindgen]
+    pub fn perft_divide(&mut self, depth: u32) -> JsValue {
+        let divide: std::collections::HashMap<String, u64> = engine::perft_divide(&mut self.board, depth)
+            .into_iter()
+            .map(|(from, to, nodes)| (move_to_uci(from, to, None), nodes))
+            .collect();
+
+        serde_wasm_bindgen::to_value(&divide).unwrap()
+    }
 }